@@ -1,18 +1,38 @@
-use std::{cell::RefCell, collections::BinaryHeap};
+use std::{
+    cell::RefCell,
+    collections::{BinaryHeap, HashMap},
+};
 
+use cassowary::{
+    strength::{REQUIRED, STRONG, WEAK},
+    Expression, Solver, Variable,
+    WeightedRelation::*,
+};
 use ratatui::{
     buffer::{Buffer, Cell},
-    layout::Rect,
-    symbols::{border, line::NORMAL},
-    widgets::Widget,
+    layout::{Alignment, Margin, Rect, Size},
+    style::Style,
+    symbols::line,
+    widgets::{BorderType, Widget},
 };
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct GridDimension {
-    ///The minimum dimension.
-    min: u16,
-    //The weight for allocating the remaining space.
-    weight: u16,
+///A constraint on the size of a single grid track (a column or a row),
+///following the same vocabulary as tui-rs/ratatui's `Constraint`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GridDimension {
+    ///A fixed size, in cells.
+    Length(u16),
+    ///A percentage of the space left over after borders and inter-track gutters are removed.
+    Percentage(u16),
+    ///A ratio (numerator, denominator) of the space left over after borders and
+    ///inter-track gutters are removed.
+    Ratio(u32, u32),
+    ///At least this many cells, growing to fill any remaining space like `Fill(1)`.
+    Min(u16),
+    ///Grows to fill remaining space like `Fill(1)`, capped at this many cells.
+    Max(u16),
+    ///Grows to fill remaining space in proportion to this weight.
+    Fill(u16),
 }
 
 //There is 1 more grid point then cell because
@@ -23,12 +43,25 @@ struct GridPoint {
     //it is occluded.
     visible: bool,
 }
+
+//Where a widget sits in grid-cell coordinates, and how it's placed inside
+//that span by `widget_rect` when the span is bigger than `size`. `size` of
+//`None` stretches the widget to fill the whole span, ignoring the alignment.
+//`vertical` reuses `Alignment`'s `Left`/`Right` variants as top/bottom.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct WidgetPlacement {
+    place: Rect,
+    horizontal: Alignment,
+    vertical: Alignment,
+    size: Option<Size>,
+}
+
 pub struct GridLayout {
     columns: Vec<GridDimension>,
     rows: Vec<GridDimension>,
     //This holds the upper left corner and the dimensions of the
-    //cells that a widget spans.
-    widget_locations: Vec<Rect>,
+    //cells that a widget spans, plus how it's aligned within them.
+    widget_locations: Vec<WidgetPlacement>,
     //This uses a refcell to enable caching the computed layout.
     edge_layout_x: RefCell<Vec<u16>>,
     edge_layout_y: RefCell<Vec<u16>>,
@@ -36,6 +69,16 @@ pub struct GridLayout {
     //Fully qualified to not conflict with Ratatui cell.
     prior_area: std::cell::Cell<Rect>,
     dirty_bit: std::cell::Cell<bool>,
+    //The glyph table used to draw edges and corners.
+    border_set: line::Set,
+    //The style applied to every drawn border `Cell`.
+    style: Style,
+    //Outer inset applied to `area` before laying out tracks.
+    margin: Margin,
+    //Extra blank space reserved between adjacent tracks, on top of their
+    //shared single-cell border. `horizontal` is between columns, `vertical`
+    //is between rows.
+    spacing: Margin,
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord)]
@@ -43,96 +86,438 @@ struct WeightItem {
     pub weight: i32,
     pub index: usize,
 }
-fn layout_grid_dim(dims: &Vec<GridDimension>, target: &mut Vec<u16>, start: u16, length: u16) {
-    target.clear();
-    let mut sizes: Vec<i32> = Vec::new();
-    for i in 0..dims.len() {
-        //There is a +1 for the border
-        sizes.push(1 + dims[i].min as i32);
-    }
-    let taken_up: i32 = sizes.iter().sum();
-    //Minus 1 for the right border
-    let mut allocate = (length as i32) - taken_up - 1;
-    let total_weight = dims.iter().map(|dim| dim.weight as i32).sum::<i32>();
-    //This bit allocates the remaining space by tracking the difference between the ideal allocation
-    //and the actual allocation. Due to fractions, matching the ideal allocation may be impossible.
-    //This uses a priority queue to get as close as possible.
-    let mut weights_heap: BinaryHeap<WeightItem> = BinaryHeap::new();
-    for (i, weight) in dims.iter().map(|dim| dim.weight as i32).enumerate() {
-        weights_heap.push(WeightItem {
-            //There are (total_weight*allocate) tokens. Each space costs
-            //total_weight tokens. The min is already allocated, so subtract it.
-            weight: weight * allocate - (total_weight * (dims[i].min as i32)),
-            index: i,
-        });
+//`Length`/`Percentage`/`Ratio`/`Max` tracks impose bounds that can conflict
+//with each other and with weighted fill; those need the joint solver. Plain
+//`Min`/`Fill` grids are handled exactly by the cheap greedy allocator.
+fn needs_solver(dims: &[GridDimension]) -> bool {
+    dims.iter().any(|dim| {
+        matches!(
+            dim,
+            GridDimension::Length(_)
+                | GridDimension::Percentage(_)
+                | GridDimension::Ratio(_, _)
+                | GridDimension::Max(_)
+        )
+    })
+}
+
+//The gutter trailing the track whose right edge is `edge_layout[end_index]`,
+//or 0 if that track is the last one (edges has no further entries).
+fn trailing_gutter(edge_layout: &[u16], end_index: usize, gutter: u16) -> u16 {
+    if end_index + 1 < edge_layout.len() {
+        gutter
+    } else {
+        0
     }
-    while allocate > 0 {
-        let Some(mut biggest) = weights_heap.pop() else {
-            return;
+}
+
+//Shrinks `span` down to `size` (clamped to fit) and positions the result
+//inside `span` per `horizontal`/`vertical`. `size: None` returns `span` unchanged.
+fn align_in(span: Rect, horizontal: Alignment, vertical: Alignment, size: Option<Size>) -> Rect {
+    let Some(size) = size else {
+        return span;
+    };
+    let width = size.width.min(span.width);
+    let height = size.height.min(span.height);
+    let x = span.x
+        + match horizontal {
+            Alignment::Left => 0,
+            Alignment::Center => (span.width - width) / 2,
+            Alignment::Right => span.width - width,
         };
-        //Since each weight was multiplied by remaining_space, there is now total_weight*remaining_space weight.
-        //So since there are remaining_space allocations each allocation costs total_weight
-        if biggest.weight > total_weight {
-            //First, do all of the positive allocations using division to be fast
-            let amount = biggest.weight / total_weight;
-            allocate -= amount;
-            sizes[biggest.index] += amount;
-            biggest.weight -= total_weight * amount;
-        } else {
-            //This allocates the remaining pixels
-            biggest.weight -= total_weight;
-            sizes[biggest.index] += 1;
-            allocate -= 1;
+    let y = span.y
+        + match vertical {
+            Alignment::Left => 0,
+            Alignment::Center => (span.height - height) / 2,
+            Alignment::Right => span.height - height,
+        };
+    Rect {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+//`gutter` is the extra blank space reserved between adjacent tracks, beyond
+//their shared single-cell border.
+fn layout_grid_dim(
+    dims: &Vec<GridDimension>,
+    target: &mut Vec<u16>,
+    start: u16,
+    length: u16,
+    gutter: u16,
+) {
+    if needs_solver(dims) {
+        layout_grid_dim_solver(dims, target, start, length, gutter);
+    } else {
+        layout_grid_dim_fast(dims, target, start, length, gutter);
+    }
+}
+
+//Only ever called for grids made of `Min`/`Fill` tracks — `needs_solver`
+//routes anything with `Length`/`Percentage`/`Ratio`/`Max` to
+//`layout_grid_dim_solver` instead, since those impose bounds a single greedy
+//pass can't jointly satisfy.
+fn layout_grid_dim_fast(
+    dims: &Vec<GridDimension>,
+    target: &mut Vec<u16>,
+    start: u16,
+    length: u16,
+    gutter: u16,
+) {
+    target.clear();
+    //There's a gutter before every track except the first.
+    let total_gutter = gutter as i32 * (dims.len() as i32 - 1).max(0);
+    let mut sizes: Vec<i32> = vec![0; dims.len()];
+    let mut weights: Vec<i32> = vec![0; dims.len()];
+    for (i, dim) in dims.iter().enumerate() {
+        match *dim {
+            GridDimension::Min(min) => {
+                sizes[i] = min as i32;
+                weights[i] = 1;
+            }
+            GridDimension::Fill(weight) => {
+                weights[i] = weight as i32;
+            }
+            GridDimension::Length(_)
+            | GridDimension::Percentage(_)
+            | GridDimension::Ratio(_, _)
+            | GridDimension::Max(_) => unreachable!("needs_solver routes this dimension kind"),
+        }
+    }
+    //There is a +1 per track for its left border.
+    let taken_up: i32 = sizes.iter().sum::<i32>() + dims.len() as i32;
+    //Minus 1 for the right border, and the inter-track gutters.
+    let allocate = (length as i32) - taken_up - 1 - total_gutter;
+    let total_weight: i32 = weights.iter().sum();
+    //This distributes the remaining space across the flexible (Min/Fill) tracks by weight.
+    if allocate > 0 && total_weight > 0 {
+        //This bit allocates the remaining space by tracking the difference between the ideal allocation
+        //and the actual allocation. Due to fractions, matching the ideal allocation may be impossible.
+        //This uses a priority queue to get as close as possible.
+        let mut weights_heap: BinaryHeap<WeightItem> = BinaryHeap::new();
+        for (i, &weight) in weights.iter().enumerate() {
+            if weight > 0 {
+                weights_heap.push(WeightItem {
+                    //There are (total_weight*allocate) tokens. Each space costs
+                    //total_weight tokens. What's already allocated is subtracted out.
+                    weight: weight * allocate - (total_weight * sizes[i]),
+                    index: i,
+                });
+            }
+        }
+        let mut remaining = allocate;
+        while remaining > 0 {
+            let Some(mut biggest) = weights_heap.pop() else {
+                break;
+            };
+            //Since each weight was multiplied by remaining_space, there is now total_weight*remaining_space weight.
+            //So since there are remaining_space allocations each allocation costs total_weight
+            if biggest.weight > total_weight {
+                //First, do all of the positive allocations using division to be fast
+                let amount = biggest.weight / total_weight;
+                remaining -= amount;
+                sizes[biggest.index] += amount;
+                biggest.weight -= total_weight * amount;
+            } else {
+                //This allocates the remaining pixels
+                biggest.weight -= total_weight;
+                sizes[biggest.index] += 1;
+                remaining -= 1;
+            }
+            weights_heap.push(biggest);
         }
-        weights_heap.push(biggest);
     }
-    assert!(allocate <= 0);
-    dbg!(&sizes);
     let mut acc = start;
     for i in 0..sizes.len() {
+        //Every track but the first is preceded by a gutter.
+        if i > 0 {
+            acc += gutter;
+        }
         target.push(acc as u16);
-        acc += sizes[i] as u16;
+        //The track's content, plus its own left border.
+        acc += sizes[i] as u16 + 1;
     }
     //For the right border.
     target.push(acc as u16);
 }
 
-fn corner_symbol(top: bool, right: bool, bottom: bool, left: bool) -> &'static str {
+//Used when `Length`/`Percentage`/`Ratio`/`Max` tracks coexist, since satisfying
+//their bounds jointly is no longer a single greedy pass. One `Variable` is
+//created per grid edge `e_0..e_n`; the edges are pinned to the track's span
+//and kept at least a border cell apart, then each track's width
+//(`e_{i+1} - e_i`, minus its own left border) gets the constraints implied by
+//its `GridDimension`. The solved edge positions are read back and rounded.
+fn layout_grid_dim_solver(
+    dims: &Vec<GridDimension>,
+    target: &mut Vec<u16>,
+    start: u16,
+    length: u16,
+    gutter: u16,
+) {
+    target.clear();
+    let edges: Vec<Variable> = (0..=dims.len()).map(|_| Variable::new()).collect();
+    let var_indices: HashMap<Variable, usize> =
+        edges.iter().enumerate().map(|(i, &v)| (v, i)).collect();
+
+    let mut solver = Solver::new();
+    solver
+        .add_constraint(edges[0] | EQ(REQUIRED) | start as f64)
+        .unwrap();
+    //Not REQUIRED: if every track is fixed-size (`Length`/`Percentage`/`Ratio`,
+    //below), their widths alone can fully pin the edge chain, leaving no
+    //flexible track to stretch to fill `length`. This just stops short then,
+    //rather than forcing a fixed track to stretch past its declared size.
+    solver
+        .add_constraint(edges[dims.len()] | EQ(STRONG) | (start as f64 + length as f64))
+        .unwrap();
+    for i in 0..dims.len() {
+        //Room for the track's left border.
+        solver
+            .add_constraint(edges[i + 1] | GE(REQUIRED) | (edges[i] + 1.0))
+            .unwrap();
+    }
+
+    //There's a gutter after every track except the last.
+    let total_gutter = gutter as i32 * (dims.len() as i32 - 1).max(0);
+    //The space left over once every track's own left border, the final right
+    //border, and the inter-track gutters are removed, used to size
+    //Percentage/Ratio tracks and as the pool the flexible tracks' targets are split from by weight.
+    let content_length = ((length as i32) - (dims.len() as i32 + 1) - total_gutter).max(0) as f64;
+    let total_weight: f64 = dims
+        .iter()
+        .map(|dim| match *dim {
+            GridDimension::Fill(weight) => weight as f64,
+            GridDimension::Min(_) | GridDimension::Max(_) => 1.0,
+            _ => 0.0,
+        })
+        .sum();
+
+    for (i, dim) in dims.iter().enumerate() {
+        //Width of the track's content: not counting its own left border, or
+        //the gutter trailing it (if any).
+        let trailing_gutter = if i + 1 < dims.len() {
+            gutter as f64
+        } else {
+            0.0
+        };
+        let width: Expression = edges[i + 1] - edges[i] - 1.0 - trailing_gutter;
+        match *dim {
+            GridDimension::Length(n) => {
+                //REQUIRED: `Length` is a fixed size, not a preference, so it
+                //must hold regardless of what else shares the grid.
+                solver
+                    .add_constraint(width | EQ(REQUIRED) | n as f64)
+                    .unwrap();
+            }
+            GridDimension::Percentage(p) => {
+                solver
+                    .add_constraint(width | EQ(REQUIRED) | (content_length * p as f64 / 100.0))
+                    .unwrap();
+            }
+            GridDimension::Ratio(numerator, denominator) => {
+                let ratio = numerator as f64 / (denominator.max(1) as f64);
+                solver
+                    .add_constraint(width | EQ(REQUIRED) | (content_length * ratio))
+                    .unwrap();
+            }
+            GridDimension::Min(min) => {
+                solver
+                    .add_constraint(width.clone() | GE(REQUIRED) | min as f64)
+                    .unwrap();
+                solver
+                    .add_constraint(width | EQ(WEAK) | (content_length / total_weight.max(1.0)))
+                    .unwrap();
+            }
+            GridDimension::Max(max) => {
+                solver
+                    .add_constraint(width.clone() | LE(REQUIRED) | max as f64)
+                    .unwrap();
+                solver
+                    .add_constraint(width | EQ(WEAK) | (content_length / total_weight.max(1.0)))
+                    .unwrap();
+            }
+            GridDimension::Fill(weight) => {
+                //Weighted fill tracks stay proportional to each other...
+                for (j, other) in dims.iter().enumerate().skip(i + 1) {
+                    if let GridDimension::Fill(other_weight) = *other {
+                        let other_trailing_gutter = if j + 1 < dims.len() {
+                            gutter as f64
+                        } else {
+                            0.0
+                        };
+                        let other_width: Expression =
+                            edges[j + 1] - edges[j] - 1.0 - other_trailing_gutter;
+                        solver
+                            .add_constraint(
+                                (width.clone() * other_weight as f64)
+                                    | EQ(WEAK)
+                                    | (other_width * weight as f64),
+                            )
+                            .unwrap();
+                    }
+                }
+                //...and land close to their weighted share of the content.
+                solver
+                    .add_constraint(
+                        width
+                            | EQ(WEAK)
+                            | (content_length * weight as f64 / total_weight.max(1.0)),
+                    )
+                    .unwrap();
+            }
+        }
+    }
+
+    let mut resolved: Vec<f64> = edges.iter().map(|_| start as f64).collect();
+    for &(var, value) in solver.fetch_changes() {
+        if let Some(&idx) = var_indices.get(&var) {
+            resolved[idx] = value;
+        }
+    }
+    for value in resolved {
+        target.push(value.round().max(0.0) as u16);
+    }
+}
+
+//`set` supplies the glyph table for the chosen `BorderType`. The 4 single-direction
+//stubs have no dedicated glyph in `line::Set`, so they stay thin in every style.
+fn corner_symbol(
+    set: &line::Set,
+    top: bool,
+    right: bool,
+    bottom: bool,
+    left: bool,
+) -> &'static str {
     match (top, right, bottom, left) {
-        (true, true, true, true) => NORMAL.cross,
-        (true, true, true, false) => NORMAL.vertical_right,
-        (true, true, false, true) => NORMAL.horizontal_up,
-        (true, true, false, false) => NORMAL.bottom_left,
-        (true, false, true, true) => NORMAL.vertical_left,
-        (true, false, true, false) => NORMAL.vertical,
-        (true, false, false, true) => NORMAL.bottom_right,
-        (true, false, false, false) => &"╵",
-        (false, true, true, true) => NORMAL.horizontal_down,
-        (false, true, true, false) => NORMAL.top_left,
-        (false, true, false, true) => NORMAL.horizontal,
-        (false, true, false, false) => &"╶",
-        (false, false, true, true) => NORMAL.top_right,
-        (false, false, true, false) => &"╷",
-        (false, false, false, true) => &"╴",
-        (false, false, false, false) => &" ",
+        (true, true, true, true) => set.cross,
+        (true, true, true, false) => set.vertical_right,
+        (true, true, false, true) => set.horizontal_up,
+        (true, true, false, false) => set.bottom_left,
+        (true, false, true, true) => set.vertical_left,
+        (true, false, true, false) => set.vertical,
+        (true, false, false, true) => set.bottom_right,
+        (true, false, false, false) => "╵",
+        (false, true, true, true) => set.horizontal_down,
+        (false, true, true, false) => set.top_left,
+        (false, true, false, true) => set.horizontal,
+        (false, true, false, false) => "╶",
+        (false, false, true, true) => set.top_right,
+        (false, false, true, false) => "╷",
+        (false, false, false, true) => "╴",
+        (false, false, false, false) => " ",
+    }
+}
+
+//Maps the ratatui-wide `BorderType` vocabulary onto the line glyph table this
+//crate draws junctions from. The quadrant variants have no sensible line-drawing
+//equivalent, so they fall back to `Plain`.
+fn border_type_to_line_set(border_type: BorderType) -> line::Set {
+    match border_type {
+        BorderType::Plain => line::NORMAL,
+        BorderType::Rounded => line::ROUNDED,
+        BorderType::Double => line::DOUBLE,
+        BorderType::Thick => line::THICK,
+        BorderType::QuadrantInside | BorderType::QuadrantOutside => line::NORMAL,
     }
 }
 
 impl GridLayout {
+    //Recomputes the layout if the area changed or a setter marked it dirty.
+    fn ensure_layout(&self, area: Rect) {
+        if self.dirty_bit.get() || area != self.prior_area.get() {
+            self.compute_layout(area);
+        }
+    }
+
+    ///Returns the screen-space `Rect` of the interior of the cell at `(col, row)`,
+    ///inside its surrounding border lines. `area` is the area the grid would be
+    ///rendered into. Returns `None` if `col`/`row` are out of bounds.
+    pub fn cell_rect(&self, area: Rect, col: usize, row: usize) -> Option<Rect> {
+        self.ensure_layout(area);
+        let edge_layout_x = self.edge_layout_x.borrow();
+        let edge_layout_y = self.edge_layout_y.borrow();
+        let x_start = *edge_layout_x.get(col)?;
+        let x_end = *edge_layout_x.get(col + 1)?;
+        let y_start = *edge_layout_y.get(row)?;
+        let y_end = *edge_layout_y.get(row + 1)?;
+        let x_gutter = trailing_gutter(&edge_layout_x, col + 1, self.spacing.horizontal);
+        let y_gutter = trailing_gutter(&edge_layout_y, row + 1, self.spacing.vertical);
+        if x_end <= x_start + 1 + x_gutter || y_end <= y_start + 1 + y_gutter {
+            return None;
+        }
+        Some(Rect {
+            x: x_start + 1,
+            y: y_start + 1,
+            width: x_end - x_start - 1 - x_gutter,
+            height: y_end - y_start - 1 - y_gutter,
+        })
+    }
+
+    ///Returns the screen-space `Rect` occupied by the widget added via
+    ///`add_widget` at `widget_index`, inside the border lines surrounding the
+    ///grid cells it spans. `area` is the area the grid would be rendered into.
+    ///If the widget was added with a `size`, the returned `Rect` is shrunk to
+    ///that size and aligned within the span per its `horizontal`/`vertical`
+    ///alignment, rather than stretched to fill it. Only the gutter trailing
+    ///the span is excluded; gutters between tracks inside the span are part
+    ///of the returned `Rect`.
+    ///Returns `None` if `widget_index` is out of bounds.
+    pub fn widget_rect(&self, area: Rect, widget_index: usize) -> Option<Rect> {
+        let placement = *self.widget_locations.get(widget_index)?;
+        let place = placement.place;
+        self.ensure_layout(area);
+        let edge_layout_x = self.edge_layout_x.borrow();
+        let edge_layout_y = self.edge_layout_y.borrow();
+        let x_start = *edge_layout_x.get(place.x as usize)?;
+        let x_end = *edge_layout_x.get(place.right() as usize)?;
+        let y_start = *edge_layout_y.get(place.y as usize)?;
+        let y_end = *edge_layout_y.get(place.bottom() as usize)?;
+        let x_gutter = trailing_gutter(
+            &edge_layout_x,
+            place.right() as usize,
+            self.spacing.horizontal,
+        );
+        let y_gutter = trailing_gutter(
+            &edge_layout_y,
+            place.bottom() as usize,
+            self.spacing.vertical,
+        );
+        if x_end <= x_start + 1 + x_gutter || y_end <= y_start + 1 + y_gutter {
+            return None;
+        }
+        let span = Rect {
+            x: x_start + 1,
+            y: y_start + 1,
+            width: x_end - x_start - 1 - x_gutter,
+            height: y_end - y_start - 1 - y_gutter,
+        };
+        Some(align_in(
+            span,
+            placement.horizontal,
+            placement.vertical,
+            placement.size,
+        ))
+    }
+
     fn compute_layout(&self, area: Rect) {
         self.dirty_bit.set(false);
         self.prior_area.set(area);
+        let area = area.inner(self.margin);
         layout_grid_dim(
-            &self.rows,
+            &self.columns,
             &mut self.edge_layout_x.borrow_mut(),
             area.x,
             area.width,
+            self.spacing.horizontal,
         );
         layout_grid_dim(
-            &self.columns,
+            &self.rows,
             &mut self.edge_layout_y.borrow_mut(),
             area.y,
             area.height,
+            self.spacing.vertical,
         );
         let grid_points = &mut *self.grid_points.borrow_mut();
 
@@ -140,8 +525,8 @@ impl GridLayout {
         let edge_layout_y = &*self.edge_layout_y.borrow();
         *grid_points =
             vec![vec![GridPoint { visible: true }; edge_layout_y.len()]; edge_layout_x.len()];
-        for location in &self.widget_locations {
-            let location = location.intersection(Rect {
+        for placement in &self.widget_locations {
+            let location = placement.place.intersection(Rect {
                 x: 0,
                 y: 0,
                 width: edge_layout_x.len() as u16,
@@ -162,24 +547,42 @@ impl GridLayout {
         let edge_layout_x = &*self.edge_layout_x.borrow();
         let edge_layout_y = &*self.edge_layout_y.borrow();
         let grid_points = &*self.grid_points.borrow();
-        //Draw the horizontal lines
+        //Draw the horizontal lines, stopping short of a track's trailing gutter.
         for i in 0..edge_layout_x.len() - 1 {
+            //The last track (i == edge_layout_x.len() - 2) has no trailing gutter.
+            let trailing_gutter = if i + 2 < edge_layout_x.len() {
+                self.spacing.horizontal
+            } else {
+                0
+            };
             for j in 0..edge_layout_y.len() {
                 if grid_points[i][j].visible && grid_points[i + 1][j].visible {
                     let y = edge_layout_y[j];
-                    for x in (edge_layout_x[i] + 1)..(edge_layout_x[i + 1]) {
-                        buf.cell_mut((x, y)).map(|c| *c = Cell::new(NORMAL.horizontal));
+                    for x in (edge_layout_x[i] + 1)..(edge_layout_x[i + 1] - trailing_gutter) {
+                        buf.cell_mut((x, y)).map(|c| {
+                            *c = Cell::new(self.border_set.horizontal);
+                            c.set_style(self.style);
+                        });
                     }
                 }
             }
         }
-        //Draw the vertical lines
+        //Draw the vertical lines, stopping short of a track's trailing gutter.
         for i in 0..edge_layout_x.len() {
             for j in 0..edge_layout_y.len() - 1 {
+                //The last track (j == edge_layout_y.len() - 2) has no trailing gutter.
+                let trailing_gutter = if j + 2 < edge_layout_y.len() {
+                    self.spacing.vertical
+                } else {
+                    0
+                };
                 if grid_points[i][j].visible && grid_points[i][j + 1].visible {
                     let x = edge_layout_x[i];
-                    for y in (edge_layout_y[j] + 1)..(edge_layout_y[j + 1]) {
-                        buf.cell_mut((x, y)).map(|c| *c = Cell::new(NORMAL.vertical));
+                    for y in (edge_layout_y[j] + 1)..(edge_layout_y[j + 1] - trailing_gutter) {
+                        buf.cell_mut((x, y)).map(|c| {
+                            *c = Cell::new(self.border_set.vertical);
+                            c.set_style(self.style);
+                        });
                     }
                 }
             }
@@ -199,8 +602,11 @@ impl GridLayout {
                 let right = grid_points.get(i + 1).is_some_and(|row| row[j].visible);
                 let bottom = grid_points[i].get(j + 1).is_some_and(|point| point.visible);
                 let left = i > 0 && grid_points[i - 1][j].visible;
-                let symbol = corner_symbol(top, right, bottom, left);
-                buf.cell_mut((edge_layout_x[i], edge_layout_y[j])).map(|c| *c = Cell::new(symbol));
+                let symbol = corner_symbol(&self.border_set, top, right, bottom, left);
+                buf.cell_mut((edge_layout_x[i], edge_layout_y[j])).map(|c| {
+                    *c = Cell::new(symbol);
+                    c.set_style(self.style);
+                });
             }
         }
     }
@@ -212,8 +618,49 @@ impl GridLayout {
         self.rows = rows;
         self.dirty_bit.set(true);
     }
-    pub fn add_widget(&mut self, place: Rect) {
-        self.widget_locations.push(place);
+    ///Adds a widget spanning the grid cells in `place` (grid-cell coordinates,
+    ///not screen space). If `size` is `Some`, `widget_rect` shrinks the span
+    ///down to that size and aligns it per `horizontal`/`vertical` instead of
+    ///stretching the widget to fill the whole span; `vertical` reuses
+    ///`Alignment`'s `Left`/`Right` variants as top/bottom. If `place` spans
+    ///more than one track, the gutters between those tracks (see
+    ///`set_spacing`) are absorbed into the span rather than left blank.
+    pub fn add_widget(
+        &mut self,
+        place: Rect,
+        horizontal: Alignment,
+        vertical: Alignment,
+        size: Option<Size>,
+    ) {
+        self.widget_locations.push(WidgetPlacement {
+            place,
+            horizontal,
+            vertical,
+            size,
+        });
+    }
+    ///Sets the glyph table used to draw edges and corners, mirroring the border
+    ///styles `Block` offers in ratatui.
+    pub fn set_border_type(&mut self, border_type: BorderType) {
+        self.border_set = border_type_to_line_set(border_type);
+    }
+    ///Sets the style applied to every drawn border cell.
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+    ///Sets the outer margin inset from `area` before laying out tracks.
+    pub fn set_margin(&mut self, margin: Margin) {
+        self.margin = margin;
+        self.dirty_bit.set(true);
+    }
+    ///Sets the blank gutter reserved between adjacent tracks, in addition to
+    ///their shared single-cell border. `spacing.horizontal` separates columns,
+    ///`spacing.vertical` separates rows. A widget spanning more than one
+    ///track (see `add_widget`) absorbs the gutters between its own tracks
+    ///into its `widget_rect`, rather than leaving them blank.
+    pub fn set_spacing(&mut self, spacing: Margin) {
+        self.spacing = spacing;
+        self.dirty_bit.set(true);
     }
     pub fn new() -> Self {
         GridLayout {
@@ -225,6 +672,10 @@ impl GridLayout {
             grid_points: RefCell::new(Vec::new()),
             prior_area: std::cell::Cell::new(Rect::ZERO),
             dirty_bit: std::cell::Cell::new(true),
+            border_set: line::NORMAL,
+            style: Style::default(),
+            margin: Margin::new(0, 0),
+            spacing: Margin::new(0, 0),
         }
     }
 }
@@ -234,9 +685,7 @@ impl Widget for &GridLayout {
     where
         Self: Sized,
     {
-        if self.dirty_bit.get() || area != self.prior_area.get() {
-            self.compute_layout(area);
-        }
+        self.ensure_layout(area);
         self.draw_edges(area, buf);
         self.draw_corners(area, buf);
     }
@@ -250,20 +699,105 @@ mod tests {
     fn render_test() {
         let mut buffer = Buffer::empty(Rect::new(0, 0, 20, 20));
         let mut layout = GridLayout::new();
-        layout.set_columns(vec![GridDimension {
-            min: 0,
-            weight: 3
-        }, GridDimension {
-            min: 2,
-            weight: 1
-        }]);
-        layout.set_rows(vec![GridDimension {
-            min: 0,
-            weight: 1
-        }; 4]);
+        layout.set_columns(vec![GridDimension::Fill(3), GridDimension::Min(2)]);
+        layout.set_rows(vec![GridDimension::Fill(1); 4]);
         layout.render(*buffer.area(), &mut buffer);
         dbg!(buffer);
         dbg!(layout.edge_layout_x);
         dbg!(layout.edge_layout_y);
     }
+
+    #[test]
+    fn cell_rect_maps_columns_to_x_and_rows_to_y() {
+        let mut layout = GridLayout::new();
+        layout.set_columns(vec![GridDimension::Fill(1); 3]);
+        layout.set_rows(vec![GridDimension::Fill(1); 2]);
+        let area = Rect::new(0, 0, 31, 21);
+        assert!(layout.cell_rect(area, 2, 0).is_some());
+        assert!(layout.cell_rect(area, 0, 2).is_none());
+        assert!(layout.cell_rect(area, 3, 0).is_none());
+    }
+
+    #[test]
+    fn layout_grid_dim_fast_distributes_min_and_fill_by_weight() {
+        //Min/Fill is the only combination `needs_solver` routes through
+        //`layout_grid_dim_fast` rather than the solver.
+        let mut layout = GridLayout::new();
+        layout.set_columns(vec![GridDimension::Min(4), GridDimension::Fill(1)]);
+        layout.set_rows(vec![GridDimension::Fill(1)]);
+        let area = Rect::new(0, 0, 20, 5);
+        let c0 = layout.cell_rect(area, 0, 0).unwrap();
+        let c1 = layout.cell_rect(area, 1, 0).unwrap();
+        assert_eq!(c0.width, 8);
+        assert_eq!(c1.width, 9);
+    }
+
+    #[test]
+    fn layout_grid_dim_solver_keeps_fixed_tracks_fixed_and_fill_tracks_flexible() {
+        let area = Rect::new(0, 0, 50, 10);
+
+        let mut layout = GridLayout::new();
+        layout.set_rows(vec![GridDimension::Length(3), GridDimension::Length(3)]);
+        layout.set_columns(vec![GridDimension::Fill(1)]);
+        assert_eq!(layout.cell_rect(area, 0, 0).unwrap().height, 3);
+        assert_eq!(layout.cell_rect(area, 0, 1).unwrap().height, 3);
+
+        let mut layout = GridLayout::new();
+        layout.set_columns(vec![GridDimension::Length(5), GridDimension::Fill(1)]);
+        layout.set_rows(vec![GridDimension::Fill(1)]);
+        let c0 = layout.cell_rect(area, 0, 0).unwrap();
+        let c1 = layout.cell_rect(area, 1, 0).unwrap();
+        assert_eq!(c0.width, 5);
+        assert!(c1.width > 5);
+    }
+
+    #[test]
+    fn border_type_changes_the_drawn_corner_glyph() {
+        let mut layout = GridLayout::new();
+        layout.set_columns(vec![GridDimension::Fill(1)]);
+        layout.set_rows(vec![GridDimension::Fill(1)]);
+        layout.set_border_type(BorderType::Rounded);
+        let area = Rect::new(0, 0, 10, 10);
+        let mut buffer = Buffer::empty(area);
+        layout.render(area, &mut buffer);
+        assert_eq!(
+            buffer.cell((0, 0)).unwrap().symbol(),
+            line::ROUNDED.top_left
+        );
+    }
+
+    #[test]
+    fn margin_and_spacing_offset_cell_rects() {
+        let mut layout = GridLayout::new();
+        layout.set_columns(vec![GridDimension::Fill(1); 2]);
+        layout.set_rows(vec![GridDimension::Fill(1)]);
+        layout.set_margin(Margin::new(2, 1));
+        layout.set_spacing(Margin::new(3, 0));
+        let area = Rect::new(0, 0, 40, 10);
+        let c0 = layout.cell_rect(area, 0, 0).unwrap();
+        let c1 = layout.cell_rect(area, 1, 0).unwrap();
+        assert_eq!(c0.x, 3);
+        assert_eq!(c0.y, 2);
+        //c1 starts after c0's content, its border, and the horizontal gutter.
+        assert_eq!(c1.x, c0.x + c0.width + 1 + 3);
+    }
+
+    #[test]
+    fn widget_rect_shrinks_and_aligns_within_its_span() {
+        let mut layout = GridLayout::new();
+        layout.set_columns(vec![GridDimension::Fill(1)]);
+        layout.set_rows(vec![GridDimension::Fill(1)]);
+        layout.add_widget(
+            Rect::new(0, 0, 1, 1),
+            Alignment::Right,
+            Alignment::Right,
+            Some(Size::new(2, 1)),
+        );
+        let area = Rect::new(0, 0, 20, 20);
+        let r = layout.widget_rect(area, 0).unwrap();
+        assert_eq!(r.width, 2);
+        assert_eq!(r.height, 1);
+        assert_eq!(r.x, 17);
+        assert_eq!(r.y, 18);
+    }
 }